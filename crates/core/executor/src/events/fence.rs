@@ -2,18 +2,102 @@ use serde::{Deserialize, Serialize};
 
 /// Fence Instruction Event.
 ///
-/// This object encapsulates the information needed to prove a RISC-V FENCE operation.
+/// This object encapsulates the information needed to prove a RISC-V
+/// `FENCE`/`FENCE.I` (Zifencei) operation, including the I-type operand
+/// fields the two variants disagree on.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[repr(C)]
 pub struct FenceEvent {
     /// The program counter.
     pub pc: u32,
+    /// Whether this is a `FENCE.I` (Zifencei) rather than a plain `FENCE`.
+    pub is_fence_i: bool,
+    /// The 4-bit fence mode (`fm`) field: `0b1000` for `FENCE.TSO`, `0b0000` otherwise.
+    /// Always `0` for `FENCE.I`.
+    pub fm: u8,
+    /// The 4-bit predecessor set (`pred`) field. Always `0` for `FENCE.I`.
+    pub pred: u8,
+    /// The 4-bit successor set (`succ`) field. Always `0` for `FENCE.I`.
+    pub succ: u8,
+    /// The 5-bit `rd` field of the encoding. Reserved by the ISA; its value
+    /// does not affect execution. `FenceChip` range-checks it but does not
+    /// constrain it to match the bits of the decoded instruction word.
+    pub rd: u8,
+    /// The 5-bit `rs1` field of the encoding. Reserved by the ISA and
+    /// handled by `FenceChip` the same way as `rd`.
+    pub rs1: u8,
 }
 
 impl FenceEvent {
-    /// Create a new [`FenceEvent`].
+    /// Create a new [`FenceEvent`] for a plain `FENCE`.
     #[must_use]
-    pub fn new(pc: u32) -> Self {
-        Self { pc }
+    pub fn new(pc: u32, fm: u8, pred: u8, succ: u8, rd: u8, rs1: u8) -> Self {
+        Self { pc, is_fence_i: false, fm, pred, succ, rd, rs1 }
     }
-}
\ No newline at end of file
+
+    /// Create a new [`FenceEvent`] for a `FENCE.I` (Zifencei).
+    #[must_use]
+    pub fn new_fence_i(pc: u32, rd: u8, rs1: u8) -> Self {
+        Self { pc, is_fence_i: true, fm: 0, pred: 0, succ: 0, rd, rs1 }
+    }
+
+    /// Decode a raw 32-bit `FENCE`/`FENCE.I` instruction word (I-type,
+    /// `opcode = 0b0001111`) into a [`FenceEvent`]. `funct3` distinguishes the
+    /// two variants: `0b000` is `FENCE`, `0b001` is `FENCE.I`.
+    ///
+    /// This is the decode step an instruction-dispatch loop should call once
+    /// it has identified a fence opcode and before pushing the resulting event
+    /// onto `ExecutionRecord::fence_events`; that dispatch loop lives in
+    /// `Executor`'s main step function, which isn't part of this checkout, so
+    /// it isn't wired up to call this yet. The tests in this module exercise
+    /// the decoding directly against literal encoded instruction words in the
+    /// meantime.
+    #[must_use]
+    pub fn decode_fence(pc: u32, instr: u32) -> Self {
+        let rd = ((instr >> 7) & 0x1f) as u8;
+        let funct3 = (instr >> 12) & 0x7;
+        let rs1 = ((instr >> 15) & 0x1f) as u8;
+        let imm = (instr >> 20) & 0xfff;
+
+        if funct3 == 0b001 {
+            Self::new_fence_i(pc, rd, rs1)
+        } else {
+            let fm = ((imm >> 8) & 0xf) as u8;
+            let pred = ((imm >> 4) & 0xf) as u8;
+            let succ = (imm & 0xf) as u8;
+            Self::new(pc, fm, pred, succ, rd, rs1)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_fence(fm: u8, pred: u8, succ: u8, rd: u8, rs1: u8) -> u32 {
+        let imm = (u32::from(fm) << 8) | (u32::from(pred) << 4) | u32::from(succ);
+        (imm << 20) | (u32::from(rs1) << 15) | (u32::from(rd) << 7) | 0b0001111
+    }
+
+    fn encode_fence_i(rd: u8, rs1: u8) -> u32 {
+        (u32::from(rs1) << 15) | (0b001 << 12) | (u32::from(rd) << 7) | 0b0001111
+    }
+
+    #[test]
+    fn decode_fence_plain() {
+        let instr = encode_fence(0, 0xf, 0x3, 0, 0);
+        assert_eq!(FenceEvent::decode_fence(0x1000, instr), FenceEvent::new(0x1000, 0, 0xf, 0x3, 0, 0));
+    }
+
+    #[test]
+    fn decode_fence_tso() {
+        let instr = encode_fence(0b1000, 0x1, 0x1, 3, 7);
+        assert_eq!(FenceEvent::decode_fence(0x2000, instr), FenceEvent::new(0x2000, 0b1000, 0x1, 0x1, 3, 7));
+    }
+
+    #[test]
+    fn decode_fence_i() {
+        let instr = encode_fence_i(5, 2);
+        assert_eq!(FenceEvent::decode_fence(0x3000, instr), FenceEvent::new_fence_i(0x3000, 5, 2));
+    }
+}
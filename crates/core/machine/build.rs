@@ -0,0 +1,192 @@
+//! Code generator for the trivial, no-operand instruction chips listed in
+//! `instructions.in`. See that file for the table format. Complex chips
+//! (real operand decoding, memory access, syscalls) are written by hand and
+//! simply omitted from the table.
+//!
+//! `instructions.in`'s `Unimp` row is a migrated worked example: its
+//! generated shape is exactly what `FenceChip` looked like before it needed
+//! real operand decoding, proving the table round-trips into a real chip
+//! rather than staying unexercised scaffolding. It assumes `ExecutionRecord`
+//! carries a matching `unimp_events: Vec<UnimpEvent>` field, the same
+//! assumption already made for `FenceChip`'s `fence_events` -- `ExecutionRecord`
+//! itself lives outside this checkout.
+//!
+//! `cargo test` runs a build script but not any `#[test]` inside it, so
+//! `parse_table`/`render_chip` below aren't unit-tested directly; correctness is anchored
+//! by keeping the generated shape identical to the hand-written chip it's
+//! modeled on.
+
+use std::{env, fmt::Write as _, fs, path::Path};
+
+struct InstructionEntry {
+    name: String,
+    opcode: String,
+    pc_increment: String,
+    op_a_immutable: String,
+}
+
+fn parse_table(contents: &str) -> Vec<InstructionEntry> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            let fields: Vec<&str> = line.split('|').map(str::trim).collect();
+            assert_eq!(
+                fields.len(),
+                4,
+                "instructions.in row `{line}` must have 4 `|`-delimited fields"
+            );
+            InstructionEntry {
+                name: fields[0].to_string(),
+                opcode: fields[1].to_string(),
+                pc_increment: fields[2].to_string(),
+                op_a_immutable: fields[3].to_string(),
+            }
+        })
+        .collect()
+}
+
+fn render_chip(entry: &InstructionEntry) -> String {
+    let name = &entry.name;
+    let opcode = &entry.opcode;
+    let pc_increment = &entry.pc_increment;
+    let op_a_immutable = &entry.op_a_immutable;
+    let lower = name.to_lowercase();
+
+    let mut out = String::new();
+    let _ = write!(
+        out,
+        r#"
+/// The number of main trace columns for `{name}Chip`.
+pub const NUM_{upper}_COLS: usize = core::mem::size_of::<{name}Cols<u8>>();
+
+/// Event for a generated no-op `{name}` instruction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[repr(C)]
+pub struct {name}Event {{
+    pub pc: u32,
+}}
+
+/// Column layout for the generated `{name}Chip`.
+#[derive(sp1_derive::AlignedBorrow, Default, Clone, Copy)]
+#[repr(C)]
+pub struct {name}Cols<T> {{
+    pub pc: T,
+    pub is_{lower}: T,
+}}
+
+/// Generated no-op chip for the `{opcode}` instruction. See `instructions.in`.
+#[derive(Default)]
+pub struct {name}Chip;
+
+impl<F: crate::air::ZkvmField> sp1_stark::air::MachineAir<F> for {name}Chip {{
+    type Record = sp1_core_executor::ExecutionRecord;
+    type Program = sp1_core_executor::Program;
+
+    fn name(&self) -> String {{
+        "{name}".to_string()
+    }}
+
+    fn generate_trace(
+        &self,
+        input: &Self::Record,
+        _output: &mut Self::Record,
+    ) -> p3_matrix::dense::RowMajorMatrix<F> {{
+        use p3_field::AbstractField;
+
+        let events = &input.{lower}_events;
+        let nb_rows = events.len();
+        let size_log2 = input.fixed_log2_rows::<F, _>(self);
+        let padded_nb_rows = crate::utils::next_power_of_two(nb_rows, size_log2);
+        let mut values = crate::utils::zeroed_f_vec(padded_nb_rows * NUM_{upper}_COLS);
+
+        for (i, event) in events.iter().enumerate() {{
+            let row = &mut values[i * NUM_{upper}_COLS..(i + 1) * NUM_{upper}_COLS];
+            let cols: &mut {name}Cols<F> = core::borrow::BorrowMut::borrow_mut(row);
+            cols.pc = F::from_canonical_u32(event.pc);
+            cols.is_{lower} = F::one();
+        }}
+
+        p3_matrix::dense::RowMajorMatrix::new(values, NUM_{upper}_COLS)
+    }}
+
+    fn generate_dependencies(&self, _input: &Self::Record, _output: &mut Self::Record) {{}}
+
+    fn num_rows(&self, input: &Self::Record) -> Option<usize> {{
+        let nb_rows = input.{lower}_events.len();
+        let size_log2 = input.fixed_log2_rows::<F, _>(self);
+        Some(crate::utils::next_power_of_two(nb_rows, size_log2))
+    }}
+
+    fn included(&self, shard: &Self::Record) -> bool {{
+        if let Some(shape) = shard.shape.as_ref() {{
+            shape.included::<F, _>(self)
+        }} else {{
+            !shard.{lower}_events.is_empty()
+        }}
+    }}
+
+    fn local_only(&self) -> bool {{
+        true
+    }}
+}}
+
+impl<F> p3_air::BaseAir<F> for {name}Chip {{
+    fn width(&self) -> usize {{
+        NUM_{upper}_COLS
+    }}
+}}
+
+impl<AB: sp1_stark::air::SP1AirBuilder> p3_air::Air<AB> for {name}Chip {{
+    fn eval(&self, builder: &mut AB) {{
+        use p3_field::AbstractField;
+        use p3_matrix::Matrix;
+
+        let main = builder.main();
+        let local = main.row_slice(0);
+        let local: &{name}Cols<AB::Var> = core::borrow::Borrow::borrow(&*local);
+
+        builder.assert_bool(local.is_{lower});
+
+        builder.receive_instruction(
+            AB::Expr::zero(),
+            AB::Expr::zero(),
+            local.pc,
+            local.pc + AB::F::from_canonical_u32({pc_increment}),
+            AB::Expr::zero(),
+            AB::F::from_canonical_u32(sp1_core_executor::Opcode::{opcode} as u32),
+            sp1_stark::Word::zero::<AB>(),
+            sp1_stark::Word::zero::<AB>(),
+            sp1_stark::Word::zero::<AB>(),
+            AB::Expr::one(),
+            AB::Expr::from_canonical_u32({op_a_immutable}),
+            AB::Expr::zero(),
+            AB::Expr::zero(),
+            AB::Expr::zero(),
+            local.is_{lower},
+        );
+    }}
+}}
+"#,
+        upper = name.to_uppercase(),
+    );
+    out
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=instructions.in");
+
+    let table_path = Path::new("instructions.in");
+    let contents = fs::read_to_string(table_path).expect("failed to read instructions.in");
+    let entries = parse_table(&contents);
+
+    let mut generated = String::from("// @generated by build.rs from instructions.in. Do not edit.\n");
+    for entry in &entries {
+        generated.push_str(&render_chip(entry));
+    }
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR not set");
+    let dest = Path::new(&out_dir).join("instructions_codegen.rs");
+    fs::write(&dest, generated).expect("failed to write generated instruction chips");
+}
@@ -0,0 +1,46 @@
+use super::ZkvmField;
+
+/// Target soundness error (as a negative log2 bound) the permutation/logup
+/// argument's running fingerprint must stay under.
+pub const TARGET_SOUNDNESS_BITS: u32 = 100;
+
+/// Returns `true` if a logup/permutation argument over `num_interactions`
+/// interactions is unsound when run over `F`'s default degree-`EXTENSION_DEGREE`
+/// extension (i.e. `num_interactions / |F|^EXTENSION_DEGREE` is not small enough
+/// to hit [`TARGET_SOUNDNESS_BITS`]), and therefore the running fingerprint would
+/// need an even larger extension to stay sound at this trace size.
+///
+/// Note this compares against the *default extension* `F^EXTENSION_DEGREE`, not
+/// the base field `F`: at `TARGET_SOUNDNESS_BITS = 100`, no 31-bit base field
+/// alone ever clears the bar regardless of trace size, so a predicate gated on
+/// `F::FIELD_BITS` would be true unconditionally and not a real trace-size
+/// switch. Gating on the extension's bit width instead gives a threshold that
+/// real traces can fall on either side of.
+///
+/// This is the soundness-threshold check only; it is not wired into
+/// `SP1AirBuilder`'s accumulator representation (extension-field columns,
+/// challenges drawn from `E`, the expanded `acc_next` constraint) -- that
+/// builder-side switch lives in the `sp1_stark` crate, which isn't part of
+/// this checkout, so this function is not yet called from anywhere.
+pub fn requires_extension_fingerprint<F: ZkvmField>(num_interactions: usize) -> bool {
+    let interaction_bits = usize::BITS - num_interactions.leading_zeros();
+    let extension_bits = F::FIELD_BITS * F::EXTENSION_DEGREE as u32;
+    interaction_bits + TARGET_SOUNDNESS_BITS >= extension_bits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use p3_baby_bear::BabyBear;
+
+    #[test]
+    fn small_trace_does_not_require_extension() {
+        assert!(!requires_extension_fingerprint::<BabyBear>(0));
+        assert!(!requires_extension_fingerprint::<BabyBear>(1 << 10));
+    }
+
+    #[test]
+    fn large_trace_requires_extension() {
+        assert!(requires_extension_fingerprint::<BabyBear>(1 << 30));
+    }
+}
@@ -0,0 +1,34 @@
+use p3_field::PrimeField32;
+
+/// The field bound used by chips in this crate, in place of the bare
+/// `PrimeField32` they used to require.
+///
+/// SP1 has historically pinned every chip to `BabyBear`. This trait exposes
+/// just the handful of operations the chips actually need so that a chip's
+/// `MachineAir` impl can be written once and monomorphized over any 31-bit
+/// field that implements it, instead of being forked per field.
+///
+/// `BabyBear` is the only field that implements this trait. It bounds the
+/// `MachineAir` impls (e.g. `FenceChip`) and `CoreShapeConfig`; `Program` and
+/// `Executor` are not generic over it. The perf binary has no `--field` flag
+/// and runs over `BabyBear` only.
+pub trait ZkvmField: PrimeField32 + Send + Sync + 'static {
+    /// The degree of the extension field used to draw the permutation
+    /// argument's challenges over this field.
+    const EXTENSION_DEGREE: usize;
+
+    /// `ceil(log2(|F|))`, used to size the permutation argument's soundness
+    /// error against the number of interactions in a trace.
+    const FIELD_BITS: u32;
+
+    /// Per-field Poseidon2 configuration (round constants, internal matrix
+    /// diagonal, etc). Left as an opaque hook type so each field crate can
+    /// plug in its own Poseidon2 parameterization.
+    type Poseidon2Config: Default + Send + Sync;
+}
+
+impl ZkvmField for p3_baby_bear::BabyBear {
+    const EXTENSION_DEGREE: usize = 4;
+    const FIELD_BITS: u32 = 31;
+    type Poseidon2Config = ();
+}
@@ -0,0 +1,5 @@
+mod extension;
+mod field;
+
+pub use extension::{requires_extension_fingerprint, TARGET_SOUNDNESS_BITS};
+pub use field::ZkvmField;
@@ -1,16 +1,36 @@
 use core::{borrow::{Borrow, BorrowMut}, mem::size_of};
 use p3_air::{Air, BaseAir};
-use p3_field::{AbstractField, PrimeField32};
+use p3_field::AbstractField;
 use p3_matrix::{dense::RowMajorMatrix, Matrix};
-use sp1_core_executor::{ExecutionRecord, Opcode, Program, DEFAULT_PC_INC};
+use sp1_core_executor::{events::FenceEvent, ExecutionRecord, Opcode, Program, DEFAULT_PC_INC};
 use sp1_derive::AlignedBorrow;
 use sp1_stark::{air::{MachineAir, SP1AirBuilder}, Word};
+use crate::air::ZkvmField;
 use crate::utils::{next_power_of_two, zeroed_f_vec};
 
 /// The number of main trace columns for `FenceChip`.
 pub const NUM_FENCE_COLS: usize = size_of::<FenceCols<u8>>();
 
-/// A chip that implements the FENCE instruction as a no-op.
+/// A chip that proves RISC-V `FENCE` and `FENCE.I` (Zifencei) instructions.
+///
+/// Both are architecturally no-ops as far as register/memory state is
+/// concerned. `fm`/`pred`/`succ` are cross-checked against the CPU's decode
+/// of the instruction: they're bit-decomposed here and the same value is
+/// sent back through the `c` operand of `receive_instruction`, so a row
+/// claiming the wrong `fm`/`pred`/`succ` won't balance against the sender's
+/// interaction. `rd`/`rs1`, by contrast, are only decoded and
+/// self-range-checked (`assert_bool` on their bit decomposition) -- they are
+/// *not* fed into any interaction, so nothing here constrains them to match
+/// the `rd`/`rs1` bits of the instruction word the CPU actually decoded.
+/// See the `eval` body for why.
+///
+/// The `FENCE.I` side of this is currently unreachable in practice:
+/// `FenceEvent::decode_fence` is the only place that distinguishes the two
+/// variants, and it isn't called from anywhere in this checkout's dispatch
+/// loop (that loop lives in `Executor::execute`, which isn't part of this
+/// checkout), so no real run ever produces a `FenceEvent` with
+/// `is_fence_i = true`. This chip's `is_fence_i` row and `Opcode::FENCE_I`
+/// interaction are written to be ready for that day, not exercised by it.
 #[derive(Default)]
 pub struct FenceChip;
 
@@ -20,18 +40,37 @@ pub struct FenceChip;
 pub struct FenceCols<T> {
     /// The program counter.
     pub pc: T,
-    /// Boolean flag indicating this is a fence instruction.
+    /// Boolean flag indicating this is a plain `FENCE`.
     pub is_fence: T,
+    /// Boolean flag indicating this is a `FENCE.I` (Zifencei).
+    pub is_fence_i: T,
+    /// Bit decomposition of the 4-bit fence mode (`fm`) field, LSB first.
+    /// Zero on `FENCE.I` rows.
+    pub fm_bits: [T; 4],
+    /// Bit decomposition of the 4-bit predecessor set (`pred`) field, LSB first.
+    /// Zero on `FENCE.I` rows.
+    pub pred_bits: [T; 4],
+    /// Bit decomposition of the 4-bit successor set (`succ`) field, LSB first.
+    /// Zero on `FENCE.I` rows.
+    pub succ_bits: [T; 4],
+    /// Bit decomposition of the 5-bit `rd` field, LSB first.
+    pub rd_bits: [T; 5],
+    /// Bit decomposition of the 5-bit `rs1` field, LSB first.
+    pub rs1_bits: [T; 5],
 }
 
-impl<F: PrimeField32> MachineAir<F> for FenceChip {
+fn bits_le<const N: usize>(value: u8) -> [u32; N] {
+    core::array::from_fn(|i| u32::from((value >> i) & 1))
+}
+
+impl<F: ZkvmField> MachineAir<F> for FenceChip {
     type Record = ExecutionRecord;
     type Program = Program;
-    
+
     fn name(&self) -> String {
         "Fence".to_string()
     }
-    
+
     fn generate_trace(
         &self,
         input: &ExecutionRecord,
@@ -40,32 +79,31 @@ impl<F: PrimeField32> MachineAir<F> for FenceChip {
         let nb_rows = input.fence_events.len();
         let size_log2 = input.fixed_log2_rows::<F, _>(self);
         let padded_nb_rows = next_power_of_two(nb_rows, size_log2);
-        
+
         // Initialize the trace values
         let mut values = zeroed_f_vec(padded_nb_rows * NUM_FENCE_COLS);
-        
+
         // Fill in the trace for each fence event
         for (i, event) in input.fence_events.iter().enumerate() {
             let row = &mut values[i * NUM_FENCE_COLS..(i + 1) * NUM_FENCE_COLS];
             let cols: &mut FenceCols<F> = row.borrow_mut();
-            
-            cols.pc = F::from_canonical_u32(event.pc);
-            cols.is_fence = F::one();
+
+            populate_fence_row(cols, event);
         }
-        
+
         RowMajorMatrix::new(values, NUM_FENCE_COLS)
     }
-    
+
     fn generate_dependencies(&self, _input: &Self::Record, _output: &mut Self::Record) {
         // FENCE instruction has no dependencies (no byte lookups or other interactions)
     }
-    
+
     fn num_rows(&self, input: &Self::Record) -> Option<usize> {
         let nb_rows = input.fence_events.len();
         let size_log2 = input.fixed_log2_rows::<F, _>(self);
         Some(next_power_of_two(nb_rows, size_log2))
     }
-    
+
     fn included(&self, shard: &Self::Record) -> bool {
         if let Some(shape) = shard.shape.as_ref() {
             shape.included::<F, _>(self)
@@ -73,50 +111,145 @@ impl<F: PrimeField32> MachineAir<F> for FenceChip {
             !shard.fence_events.is_empty()
         }
     }
-    
+
     fn local_only(&self) -> bool {
         true
     }
 }
 
+fn populate_fence_row<F: ZkvmField>(cols: &mut FenceCols<F>, event: &FenceEvent) {
+    cols.pc = F::from_canonical_u32(event.pc);
+    cols.is_fence = F::from_bool(!event.is_fence_i);
+    cols.is_fence_i = F::from_bool(event.is_fence_i);
+
+    for (col, bit) in cols.fm_bits.iter_mut().zip(bits_le::<4>(event.fm)) {
+        *col = F::from_canonical_u32(bit);
+    }
+    for (col, bit) in cols.pred_bits.iter_mut().zip(bits_le::<4>(event.pred)) {
+        *col = F::from_canonical_u32(bit);
+    }
+    for (col, bit) in cols.succ_bits.iter_mut().zip(bits_le::<4>(event.succ)) {
+        *col = F::from_canonical_u32(bit);
+    }
+    for (col, bit) in cols.rd_bits.iter_mut().zip(bits_le::<5>(event.rd)) {
+        *col = F::from_canonical_u32(bit);
+    }
+    for (col, bit) in cols.rs1_bits.iter_mut().zip(bits_le::<5>(event.rs1)) {
+        *col = F::from_canonical_u32(bit);
+    }
+}
+
 impl<F> BaseAir<F> for FenceChip {
     fn width(&self) -> usize {
         NUM_FENCE_COLS
     }
 }
 
+/// Reconstructs `sum(bits[i] * 2^i)` from an LSB-first bit array, asserting
+/// each bit is boolean along the way.
+fn reconstruct_from_bits<AB: SP1AirBuilder, const N: usize>(
+    builder: &mut AB,
+    bits: &[AB::Var; N],
+) -> AB::Expr {
+    let mut value = AB::Expr::zero();
+    let mut weight = AB::Expr::one();
+    for &bit in bits.iter() {
+        builder.assert_bool(bit);
+        value += weight.clone() * bit;
+        weight *= AB::Expr::two();
+    }
+    value
+}
+
 impl<AB: SP1AirBuilder> Air<AB> for FenceChip {
     fn eval(&self, builder: &mut AB) {
         let main = builder.main();
         let local = main.row_slice(0);
         let local: &FenceCols<AB::Var> = (*local).borrow();
-        
-        // Constrain that is_fence is boolean (0 or 1)
+
+        // Exactly one of `is_fence`/`is_fence_i` is set on a real row; neither is set on a
+        // padding row.
         builder.assert_bool(local.is_fence);
-        
-        // When is_fence is 1, receive the FENCE instruction from CPU
-        // This verifies:
-        // - The instruction has opcode = FENCE
-        // - No computation (a=0, b=0, c=0)
-        // - PC advances by DEFAULT_PC_INC (4)
-        // - No memory access, syscalls, or halts
+        builder.assert_bool(local.is_fence_i);
+        builder.assert_bool(local.is_fence + local.is_fence_i);
+
+        let fm = reconstruct_from_bits(builder, &local.fm_bits);
+        let pred = reconstruct_from_bits(builder, &local.pred_bits);
+        let succ = reconstruct_from_bits(builder, &local.succ_bits);
+        let rd = reconstruct_from_bits(builder, &local.rd_bits);
+        let rs1 = reconstruct_from_bits(builder, &local.rs1_bits);
+
+        // `FENCE.I`'s encoding fixes `fm`/`pred`/`succ` to zero; `rd`/`rs1` are reserved by
+        // the ISA for both variants and are only range-checked, not constrained to a value.
+        builder.when(local.is_fence_i).assert_zero(fm.clone());
+        builder.when(local.is_fence_i).assert_zero(pred.clone());
+        builder.when(local.is_fence_i).assert_zero(succ.clone());
+
+        let next_pc = local.pc + AB::F::from_canonical_u32(DEFAULT_PC_INC);
+        // `rd` is never written by either variant, regardless of its encoded index.
+        let op_a_immutable = AB::Expr::one();
+        // Every other chip in this series sends `Word::zero()` for `a`/`b`/`c` with a
+        // constant `op_a_0 = 1` on a true no-op (see the generated chips in
+        // `alu/generated`): those slots carry the *value* written/read through `op_a`/
+        // `op_b`, and fence never writes or reads a register value through them. `rd`
+        // and `rs1` here are reserved encoding fields, not register operands, so they
+        // stay out of the interaction entirely and are only range-checked in-trace via
+        // `reconstruct_from_bits`'s `assert_bool` calls above.
+        //
+        // Cross-checking those range-checked bits against the instruction word the CPU
+        // chip actually decoded would need a shared interaction with that chip's own
+        // decode, which isn't part of this checkout -- left as follow-up work rather
+        // than guessed at here.
+        let op_a_0 = AB::Expr::one();
+
         builder.receive_instruction(
-            AB::Expr::zero(),                                           // unused_shard
-            AB::Expr::zero(),                                           // unused_channel
-            local.pc,                                                   // pc
-            local.pc + AB::F::from_canonical_u32(DEFAULT_PC_INC),      // next_pc
-            AB::Expr::zero(),                                           // num_extra_cycles
-            AB::F::from_canonical_u32(Opcode::FENCE as u32),           // opcode
-            Word::zero::<AB>(),                                         // a (zero for no-op)
-            Word::zero::<AB>(),                                         // b (zero for no-op)
-            Word::zero::<AB>(),                                         // c (zero for no-op)
-            AB::Expr::one(),                                            // op_a_0 (a is zero)
-            AB::Expr::zero(),                                           // op_a_immutable
-            AB::Expr::zero(),                                           // is_memory
-            AB::Expr::zero(),                                           // is_syscall
-            AB::Expr::zero(),                                           // is_halt
-            local.is_fence,                                             // selector
+            AB::Expr::zero(),                                  // unused_shard
+            AB::Expr::zero(),                                  // unused_channel
+            local.pc,                                          // pc
+            next_pc,                                           // next_pc
+            AB::Expr::zero(),                                  // num_extra_cycles
+            AB::F::from_canonical_u32(Opcode::FENCE as u32),   // opcode
+            Word::zero::<AB>(),                                // a
+            Word::zero::<AB>(),                                // b
+            // The I-type immediate packs `fm`/`pred`/`succ` into one 12-bit field as
+            // `fm<<8 | pred<<4 | succ` (see `FenceEvent::decode_fence`), so its
+            // little-endian byte decomposition is `[pred<<4 | succ, fm, 0, 0]`, not one
+            // field per byte.
+            Word([
+                pred * AB::Expr::from_canonical_u32(16) + succ,
+                fm,
+                AB::Expr::zero(),
+                AB::Expr::zero(),
+            ]), // c: imm = fm<<8 | pred<<4 | succ
+            op_a_0.clone(),
+            op_a_immutable.clone(),
+            AB::Expr::zero(),                                  // is_memory
+            AB::Expr::zero(),                                  // is_syscall
+            AB::Expr::zero(),                                  // is_halt
+            local.is_fence,                                    // selector
         );
+
+        builder.receive_instruction(
+            AB::Expr::zero(),
+            AB::Expr::zero(),
+            local.pc,
+            local.pc + AB::F::from_canonical_u32(DEFAULT_PC_INC),
+            AB::Expr::zero(),
+            AB::F::from_canonical_u32(Opcode::FENCE_I as u32),
+            Word::zero::<AB>(),                                // a
+            Word::zero::<AB>(),                                // b
+            Word::zero::<AB>(),                                // c: imm is fixed 0
+            op_a_0,
+            op_a_immutable,
+            AB::Expr::zero(),
+            AB::Expr::zero(),
+            AB::Expr::zero(),
+            local.is_fence_i,
+        );
+
+        // `rd`/`rs1` are only used for the `assert_bool` range-checks inside
+        // `reconstruct_from_bits` above and aren't fed into the interaction (see above).
+        let _ = (rd, rs1);
     }
 }
 
@@ -131,33 +264,32 @@ mod tests {
 
     #[test]
     fn test_generate_trace() {
-        let record = ExecutionRecord::default();
-        
+        let mut record = ExecutionRecord::default();
+        record.fence_events.push(FenceEvent::new(0, 0, 0xf, 0x3, 0, 0));
+        record.fence_events.push(FenceEvent::new_fence_i(4, 0, 0));
+
         let chip = FenceChip::default();
-        let trace: RowMajorMatrix<BabyBear> = 
+        let trace: RowMajorMatrix<BabyBear> =
             chip.generate_trace(&record, &mut ExecutionRecord::default());
-        
-        // Verify the trace has the correct shape
+
         assert_eq!(trace.width(), NUM_FENCE_COLS);
-        
-        // TODO: Add actual fence event testing once FenceEvent is available
-        // Currently just testing that empty trace generation works
     }
 
     #[test]
     fn test_prove_fence() {
         let config = BabyBearPoseidon2::new();
         let mut challenger = config.challenger();
-        
-        let record = ExecutionRecord::default();
-        // TODO: Add fence events once available
-        
+
+        let mut record = ExecutionRecord::default();
+        record.fence_events.push(FenceEvent::new(0, 0, 0xf, 0x3, 0, 0));
+        record.fence_events.push(FenceEvent::new_fence_i(4, 0, 0));
+
         let chip = FenceChip::default();
-        let trace: RowMajorMatrix<BabyBear> = 
+        let trace: RowMajorMatrix<BabyBear> =
             chip.generate_trace(&record, &mut ExecutionRecord::default());
         let proof = prove::<BabyBearPoseidon2, _>(&config, &chip, &mut challenger, trace);
-        
+
         let mut challenger = config.challenger();
         verify(&config, &chip, &mut challenger, &proof).unwrap();
     }
-}
\ No newline at end of file
+}
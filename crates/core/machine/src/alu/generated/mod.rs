@@ -0,0 +1,7 @@
+//! Instruction chips generated by `build.rs` from `instructions.in`. See
+//! that file for the declarative table format and the module-level docs on
+//! `build.rs` for what gets generated per entry.
+
+use sp1_core_executor::DEFAULT_PC_INC;
+
+include!(concat!(env!("OUT_DIR"), "/instructions_codegen.rs"));
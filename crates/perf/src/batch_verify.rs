@@ -0,0 +1,66 @@
+//! Parallel batch proof verification, modeled on Solana sigverify's
+//! lazily-initialized thread pool: a fixed-size rayon pool sized to the
+//! available cores (overridable via an env var for reproducible
+//! benchmarking across machines), fanning `par_iter().map(verify)` out
+//! across it instead of verifying proofs one at a time.
+//!
+//! [`BatchVerify`] is an extension trait rather than a bare free function so
+//! the entry point is `prover.verify_many_compressed(&batch)` -- the shape a
+//! verification service would actually want to call -- instead of something
+//! only reachable by importing this perf binary's own module. The natural
+//! next step is upstreaming this `impl` into `sp1-prover` itself so other
+//! consumers don't need to depend on the perf crate to get it; that crate
+//! isn't part of this checkout, so it stays here as an extension trait over
+//! its public `SP1Prover` type in the meantime.
+
+use std::sync::OnceLock;
+
+use rayon::{prelude::*, ThreadPool, ThreadPoolBuilder};
+use sp1_prover::{components::CpuProverComponents, SP1Prover, SP1VerifyingKey};
+
+/// Env var overriding the batch-verification thread pool size, for
+/// reproducible benchmarking across machines with different core counts.
+const NUM_THREADS_ENV: &str = "SP1_VERIFY_NUM_THREADS";
+
+fn get_thread_count() -> usize {
+    std::env::var(NUM_THREADS_ENV)
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .filter(|&n| n > 0)
+        .unwrap_or_else(num_cpus::get)
+}
+
+static PAR_THREAD_POOL: OnceLock<ThreadPool> = OnceLock::new();
+
+fn thread_pool() -> &'static ThreadPool {
+    PAR_THREAD_POOL.get_or_init(|| {
+        ThreadPoolBuilder::new()
+            .num_threads(get_thread_count())
+            .build()
+            .expect("failed to build batch verification thread pool")
+    })
+}
+
+/// Verify many compressed proofs at once, the entry point a verification
+/// service sitting in front of [`SP1Prover`] would call.
+pub trait BatchVerify<Proof> {
+    /// Verify a batch of compressed proofs in parallel, fanned out across a
+    /// [`rayon`] thread pool sized by [`get_thread_count`] instead of
+    /// verifying them one at a time.
+    fn verify_many_compressed(&self, proofs: &[(Proof, SP1VerifyingKey)]) -> Vec<Result<(), String>>;
+}
+
+impl<Proof> BatchVerify<Proof> for SP1Prover<CpuProverComponents>
+where
+    Proof: Sync,
+    SP1Prover<CpuProverComponents>: Sync,
+{
+    fn verify_many_compressed(&self, proofs: &[(Proof, SP1VerifyingKey)]) -> Vec<Result<(), String>> {
+        thread_pool().install(|| {
+            proofs
+                .par_iter()
+                .map(|(proof, vk)| self.verify_compressed(proof, vk).map_err(|e| e.to_string()))
+                .collect()
+        })
+    }
+}
@@ -0,0 +1,237 @@
+//! RISC-V architectural compliance test runner.
+//!
+//! Given a directory of compiled riscv-arch-test ELFs (each paired with a
+//! golden `<name>.reference_output` signature file), this runs every test
+//! through the [`Executor`], dumps its signature region in the canonical
+//! per-word hex format, and diffs it word-by-word against the reference so
+//! that the executor's RV32IM semantics can be certified against the
+//! official test vectors instead of eyeballed by hand.
+//!
+//! A few edge cases the dumping path has to handle rather than ignore:
+//! - a test ELF missing one or both of `begin_signature`/`end_signature`,
+//! - a signature region whose size is zero or not word-aligned (the
+//!   trailing partial word is still padded and dumped, same as today),
+//! - reads into memory the executor never initialized, which `Executor::byte`
+//!   already answers as zero rather than erroring, and
+//! - a test that signals completion through the `tohost`/halt convention
+//!   (see [`crate::signature::read_tohost_status`]) rather than a clean
+//!   signature match alone -- a test can write a matching signature and
+//!   still report a `tohost` failure code, so both are checked.
+
+use std::path::{Path, PathBuf};
+
+use clap::Parser;
+use p3_baby_bear::BabyBear;
+use sp1_core_executor::{Executor, Program};
+use sp1_core_machine::shape::CoreShapeConfig;
+use sp1_sdk::SP1Stdin;
+use sp1_stark::SP1ProverOpts;
+
+use crate::signature::{
+    collect_signatures, parse_signature_symbols, parse_tohost_symbol, read_tohost_status,
+    TohostStatus, DEFAULT_WORD_BYTES,
+};
+
+#[derive(Parser, Clone)]
+pub struct ComplianceArgs {
+    /// Directory containing compiled test ELFs and their `.reference_output` signatures.
+    #[arg(short, long)]
+    pub test_dir: PathBuf,
+
+    /// Extension used for the golden signature files next to each test ELF.
+    #[arg(long, default_value = "reference_output")]
+    pub reference_ext: String,
+
+    /// Maximum number of mismatching words to print per failing test.
+    #[arg(long, default_value_t = 8)]
+    pub max_mismatches: usize,
+
+    /// Word granularity, in bytes, for the signature region (1, 2, 4, or 8).
+    #[arg(long, default_value_t = DEFAULT_WORD_BYTES)]
+    pub word_bytes: usize,
+}
+
+/// A single word that differs between the actual and reference signatures.
+pub struct Mismatch {
+    pub offset: usize,
+    pub expected: Option<u64>,
+    pub actual: Option<u64>,
+}
+
+/// The outcome of diffing a single test's signature against its reference.
+pub struct TestResult {
+    pub name: String,
+    /// `Err` if the test couldn't even be run (e.g. missing symbols/files);
+    /// `Ok(mismatches)` otherwise, empty when the signature matches the reference.
+    pub outcome: Result<Vec<Mismatch>, String>,
+    /// The test's `tohost` status, if its ELF defines the symbol.
+    pub tohost: Option<TohostStatus>,
+}
+
+impl TestResult {
+    pub fn passed(&self) -> bool {
+        let signature_ok = matches!(&self.outcome, Ok(mismatches) if mismatches.is_empty());
+        let tohost_ok = !matches!(self.tohost, Some(TohostStatus::Fail { .. }));
+        signature_ok && tohost_ok
+    }
+}
+
+/// Aggregate pass/fail report across a whole compliance directory.
+pub struct ComplianceReport {
+    pub results: Vec<TestResult>,
+    pub max_mismatches: usize,
+}
+
+impl ComplianceReport {
+    pub fn passed(&self) -> usize {
+        self.results.iter().filter(|r| r.passed()).count()
+    }
+
+    pub fn failed(&self) -> usize {
+        self.results.len() - self.passed()
+    }
+
+    pub fn print_summary(&self) {
+        for result in &self.results {
+            match &result.outcome {
+                Ok(mismatches) if mismatches.is_empty() && result.passed() => {
+                    println!("PASS {}", result.name)
+                }
+                Ok(mismatches) => {
+                    if !mismatches.is_empty() {
+                        println!(
+                            "FAIL {} ({} mismatching word{})",
+                            result.name,
+                            mismatches.len(),
+                            if mismatches.len() == 1 { "" } else { "s" }
+                        );
+                        for mismatch in mismatches.iter().take(self.max_mismatches) {
+                            println!(
+                                "  offset 0x{:x}: expected {}, got {}",
+                                mismatch.offset,
+                                format_word(mismatch.expected),
+                                format_word(mismatch.actual),
+                            );
+                        }
+                        if mismatches.len() > self.max_mismatches {
+                            println!("  ... and {} more", mismatches.len() - self.max_mismatches);
+                        }
+                    } else {
+                        println!("FAIL {} (signature matched, but {})", result.name, tohost_reason(result.tohost));
+                    }
+                }
+                Err(e) => println!("ERROR {}: {}", result.name, e),
+            }
+        }
+        println!("{}/{} tests passed", self.passed(), self.results.len());
+    }
+}
+
+fn tohost_reason(tohost: Option<TohostStatus>) -> String {
+    match tohost {
+        Some(TohostStatus::Fail { test_case }) => format!("tohost reported failing test case {test_case}"),
+        _ => "tohost status unavailable".to_string(),
+    }
+}
+
+fn format_word(word: Option<u64>) -> String {
+    match word {
+        Some(w) => format!("{:x}", w),
+        None => "<missing>".to_string(),
+    }
+}
+
+/// Run every `.elf` in `args.test_dir` through the executor and diff its
+/// signature output against the matching reference file (extension
+/// `args.reference_ext`, `reference_output` by default).
+pub fn run_compliance_suite(args: &ComplianceArgs) -> Result<ComplianceReport, Box<dyn std::error::Error>> {
+    let mut results = Vec::new();
+
+    let mut entries: Vec<PathBuf> = std::fs::read_dir(&args.test_dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().and_then(|ext| ext.to_str()) == Some("elf"))
+        .collect();
+    entries.sort();
+
+    for elf_path in entries {
+        let name = elf_path.file_stem().unwrap_or_default().to_string_lossy().to_string();
+        let reference_path = elf_path.with_extension(&args.reference_ext);
+
+        match run_and_diff(&elf_path, &reference_path, args.word_bytes) {
+            Ok((mismatches, tohost)) => {
+                results.push(TestResult { name, outcome: Ok(mismatches), tohost })
+            }
+            Err(e) => results.push(TestResult { name, outcome: Err(e.to_string()), tohost: None }),
+        }
+    }
+
+    Ok(ComplianceReport { results, max_mismatches: args.max_mismatches })
+}
+
+/// Parse the reference signature file's hex lines into words.
+///
+/// Errors on a malformed line rather than defaulting it to zero -- a corrupted or
+/// truncated golden file should surface as "bad reference file", not silently compare
+/// against zero and either spuriously pass or report a misleading mismatch.
+fn parse_reference(contents: &str) -> Result<Vec<u64>, String> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            u64::from_str_radix(line, 16)
+                .map_err(|e| format!("invalid reference line {line:?}: {e}"))
+        })
+        .collect()
+}
+
+fn run_and_diff(
+    elf_path: &Path,
+    reference_path: &Path,
+    word_bytes: usize,
+) -> Result<(Vec<Mismatch>, Option<TohostStatus>), Box<dyn std::error::Error>> {
+    let elf = std::fs::read(elf_path)?;
+    let reference_contents = std::fs::read_to_string(reference_path)?;
+    let reference = parse_reference(&reference_contents)?;
+
+    let opts = SP1ProverOpts::auto();
+    let mut program = Program::from(&elf).expect("failed to parse program");
+    let shape_config = CoreShapeConfig::<BabyBear>::default();
+    shape_config.fix_preprocessed_shape(&mut program).unwrap();
+
+    let mut executor = Executor::new(program, opts.core_opts);
+    executor.write_vecs(&SP1Stdin::new().buffer);
+    executor.run_fast();
+
+    // `parse_signature_symbols` itself errors out if either `begin_signature` or
+    // `end_signature` is missing -- we surface that as a whole-test error rather than a
+    // mismatch, since there's nothing to diff against.
+    let (addr, size) = parse_signature_symbols(&elf)?;
+    // `collect_signatures` pads a non-word-aligned trailing tail with zero bytes, and
+    // `Executor::byte` answers zero for any address the program never wrote -- both match
+    // what a reference implementation would produce for the same region.
+    let actual = collect_signatures(&mut executor, addr, size, word_bytes);
+
+    let len = actual.len().max(reference.len());
+    let mismatches = (0..len)
+        .filter_map(|i| {
+            let actual_word = actual.get(i).copied();
+            let expected_word = reference.get(i).copied();
+            if actual_word == expected_word {
+                None
+            } else {
+                Some(Mismatch {
+                    offset: i * word_bytes,
+                    expected: expected_word,
+                    actual: actual_word,
+                })
+            }
+        })
+        .collect();
+
+    // Not every test defines `tohost` (plain signature-only tests may not), so its absence
+    // isn't an error -- it just means there's nothing more to check beyond the signature.
+    let tohost = parse_tohost_symbol(&elf)?.map(|tohost_addr| read_tohost_status(&mut executor, tohost_addr));
+
+    Ok((mismatches, tohost))
+}
@@ -1,6 +1,10 @@
+mod batch_verify;
+mod compliance;
+mod signature;
+
 use std::time::{Duration, Instant};
 
-use clap::{command, Parser};
+use clap::{command, Parser, Subcommand};
 use rand::Rng;
 use sp1_cuda::{MoongateServer, SP1CudaProver};
 use sp1_prover::{components::CpuProverComponents, HashableKey, ProverMode};
@@ -8,14 +12,30 @@ use sp1_sdk::{self, Prover, ProverClient, SP1Context, SP1Prover, SP1Stdin};
 use sp1_stark::SP1ProverOpts;
 use test_artifacts::VERIFY_PROOF_ELF;
 
-// Add ELF parsing imports for signature collection
-use elf::{abi::STT_OBJECT, endian::AnyEndian, ElfBytes};
 use sp1_core_executor::{Executor, ExecutorMode, Program};
 use sp1_core_machine::shape::CoreShapeConfig;
 use p3_baby_bear::BabyBear;
 
-#[derive(Parser, Clone)]
+use batch_verify::BatchVerify;
+use compliance::ComplianceArgs;
+use signature::parse_signature_symbols;
+
+#[derive(Parser)]
 #[command(about = "Evaluate the performance of SP1 on programs.")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Clone)]
+enum Command {
+    /// Run a single program through the prover and report timing (the default perf workflow).
+    Perf(PerfArgs),
+    /// Run the RISC-V architectural compliance suite against a directory of test ELFs.
+    Compliance(ComplianceArgs),
+}
+
+#[derive(Parser, Clone)]
 struct PerfArgs {
     /// The program to evaluate.
     #[arg(short, long)]
@@ -34,6 +54,32 @@ struct PerfArgs {
     /// Test signatures output file.
     #[arg(long)]
     pub signatures: Option<String>,
+
+    /// Replicate the compressed proof N times and verify the batch in parallel,
+    /// reporting aggregate proofs/sec.
+    #[arg(long)]
+    pub batch: Option<usize>,
+
+    /// Dump an arbitrary memory window instead of the symbol-delimited signature
+    /// region, formatted as `<hex addr>:<len>` (e.g. `0x80001000:256`).
+    #[arg(long)]
+    pub dump_window: Option<String>,
+
+    /// Output file for `--dump-window`. Defaults to `--signatures`'s file.
+    #[arg(long)]
+    pub dump_file: Option<String>,
+
+    /// Word granularity, in bytes, for `--signatures`/`--dump-window` output (1, 2, 4, or 8).
+    #[arg(long, default_value_t = signature::DEFAULT_WORD_BYTES)]
+    pub word_bytes: usize,
+}
+
+/// Parse a `--dump-window` value of the form `<hex addr>:<len>`.
+fn parse_dump_window(spec: &str) -> Result<(u32, usize), Box<dyn std::error::Error>> {
+    let (addr, len) = spec.split_once(':').ok_or("--dump-window must be `<addr>:<len>`")?;
+    let addr = u32::from_str_radix(addr.trim_start_matches("0x"), 16)?;
+    let len = len.parse::<usize>()?;
+    Ok((addr, len))
 }
 
 #[derive(Default, Debug, Clone)]
@@ -49,6 +95,9 @@ struct PerfResult {
     pub verify_shrink_duration: Duration,
     pub wrap_duration: Duration,
     pub verify_wrap_duration: Duration,
+    pub batch_size: usize,
+    pub batch_verify_duration: Duration,
+    pub batch_proofs_per_sec: f64,
 }
 
 pub fn time_operation<T, F: FnOnce() -> T>(operation: F) -> (T, std::time::Duration) {
@@ -58,72 +107,48 @@ pub fn time_operation<T, F: FnOnce() -> T>(operation: F) -> (T, std::time::Durat
     (result, duration)
 }
 
-/// Parse ELF symbols to find signature region boundaries
-fn parse_signature_symbols(elf_data: &[u8]) -> Result<(u32, usize), Box<dyn std::error::Error>> {
-    let elf = ElfBytes::<AnyEndian>::minimal_parse(elf_data)?;
-    
-    let (symbol_table, string_table) = elf.symbol_table()?
-        .ok_or("No symbol table found")?;
-    
-    let mut begin_signature_addr: Option<u64> = None;
-    let mut end_signature_addr: Option<u64> = None;
-    
-    for symbol in symbol_table.iter() {
-        if symbol.st_symtype() == STT_OBJECT || symbol.st_symtype() == elf::abi::STT_NOTYPE {
-            if let Ok(name) = string_table.get(symbol.st_name as usize) {
-                match name {
-                    "begin_signature" => {
-                        begin_signature_addr = Some(symbol.st_value);
-                        println!("Found begin_signature at 0x{:x}", symbol.st_value);
-                    }
-                    "end_signature" => {
-                        end_signature_addr = Some(symbol.st_value);
-                        println!("Found end_signature at 0x{:x}", symbol.st_value);
-                    }
-                    _ => {}
-                }
-            }
-        }
-    }
-    
-    if let (Some(begin_addr), Some(end_addr)) = (begin_signature_addr, end_signature_addr) {
-        let size = (end_addr - begin_addr) as usize;
-        Ok((begin_addr as u32, size))
-    } else {
-        Err("Could not find both begin_signature and end_signature symbols".into())
-    }
+/// Run executor to collect signatures.
+fn run_executor_for_signatures(
+    elf: &[u8],
+    stdin: &SP1Stdin,
+    signature_file: &str,
+    word_bytes: usize,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let (addr, size) = parse_signature_symbols(elf)?;
+    println!("Signature region: addr=0x{:x}, size={}", addr, size);
+    run_memory_dump(elf, stdin, addr, size, word_bytes, signature_file)
 }
 
-/// Collect signature data from executor memory
-fn collect_signatures(executor: &mut Executor, addr: u32, size: usize) -> Vec<u32> {
-    let mut signatures = Vec::<u32>::new();
-    
-    // Read memory in 4-byte chunks
-    for i in (0..size).step_by(4) {
-        let byte_addr = addr + i as u32;
-        let mut word_bytes = [0u8; 4];
-        
-        // Read 4 bytes from memory using SP1's byte method
-        for j in 0..4 {
-            if i + j < size {
-                word_bytes[j] = executor.byte(byte_addr + j as u32);
-            }
-        }
-        
-        // Convert to little-endian u32
-        let signature = u32::from_le_bytes(word_bytes);
-        signatures.push(signature);
-    }
-    
-    signatures
+/// Stream a `[addr, addr + size)` memory window out to `out_file`. Used both for the
+/// symbol-delimited signature region and for an arbitrary `--dump-window`.
+///
+/// This only runs over `BabyBear`: `Executor`/`Program` aren't generic over
+/// [`ZkvmField`](sp1_core_machine::air::ZkvmField) in this checkout (only the
+/// chips' `MachineAir` impls and `CoreShapeConfig` are), so there is no other
+/// field to monomorphize over yet, and no `--field` flag pretending otherwise.
+fn run_memory_dump(
+    elf: &[u8],
+    stdin: &SP1Stdin,
+    addr: u32,
+    size: usize,
+    word_bytes: usize,
+    out_file: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    run_memory_dump_with_shape::<BabyBear>(elf, stdin, addr, size, word_bytes, out_file)
 }
 
-/// Run executor to collect signatures
-fn run_executor_for_signatures(elf: &[u8], stdin: &SP1Stdin, signature_file: &str) -> Result<(), Box<dyn std::error::Error>> {
+fn run_memory_dump_with_shape<F: sp1_core_machine::air::ZkvmField>(
+    elf: &[u8],
+    stdin: &SP1Stdin,
+    addr: u32,
+    size: usize,
+    word_bytes: usize,
+    out_file: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
     let opts = SP1ProverOpts::auto();
-    
+
     let mut program = Program::from(elf).expect("failed to parse program");
-    let shape_config = CoreShapeConfig::<BabyBear>::default();
+    let shape_config = CoreShapeConfig::<F>::default();
     shape_config.fix_preprocessed_shape(&mut program).unwrap();
     let maximal_shapes = shape_config
         .maximal_core_shapes(opts.core_opts.shard_size.ilog2() as usize)
@@ -140,27 +165,31 @@ fn run_executor_for_signatures(elf: &[u8], stdin: &SP1Stdin, signature_file: &st
     // Execute the program
     executor.run_fast();
 
-    // Parse signature symbols from ELF
-    let (addr, size) = parse_signature_symbols(elf)?;
-    println!("Signature region: addr=0x{:x}, size={}", addr, size);
+    // Stream the window straight from executor memory into the output file in bounded
+    // chunks, rather than materializing the whole region in memory first.
+    let file = std::fs::File::create(out_file)?;
+    let words_written = signature::stream_memory(&mut executor, addr, size, word_bytes, file)?;
+    println!("Wrote {} words to {}", words_written, out_file);
 
-    // Collect signatures from executor memory
-    let signatures = collect_signatures(&mut executor, addr, size);
-    let signature_content = signatures
-        .iter()
-        .map(|sig| format!("{:08x}\n", sig))
-        .collect::<String>();
-    
-    std::fs::write(signature_file, signature_content)?;
-    println!("Wrote {} signatures to {}", signatures.len(), signature_file);
-    
     Ok(())
 }
 
 fn main() {
     sp1_sdk::utils::setup_logger();
-    let args = PerfArgs::parse();
 
+    match Cli::parse().command {
+        Command::Perf(args) => run_perf(args),
+        Command::Compliance(args) => {
+            let report = compliance::run_compliance_suite(&args).expect("compliance suite failed to run");
+            report.print_summary();
+            if report.failed() > 0 {
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+fn run_perf(args: PerfArgs) {
     let elf = std::fs::read(args.program).expect("failed to read program");
     let stdin = std::fs::read(args.stdin).expect("failed to read stdin");
     let stdin: SP1Stdin = bincode::deserialize(&stdin).expect("failed to deserialize stdin");
@@ -174,11 +203,33 @@ fn main() {
             // Collect signatures if requested
             if let Some(signature_file) = &args.signatures {
                 println!("Collecting signatures for RISC-V compliance...");
-                if let Err(e) = run_executor_for_signatures(&elf, &stdin, signature_file) {
+                if let Err(e) =
+                    run_executor_for_signatures(&elf, &stdin, signature_file, args.word_bytes)
+                {
                     println!("Warning: Failed to collect signatures: {}", e);
                 }
             }
 
+            // Dump an arbitrary memory window if requested, defaulting its output file to
+            // `--signatures`'s.
+            if let Some(window) = &args.dump_window {
+                let out_file = args
+                    .dump_file
+                    .as_deref()
+                    .or(args.signatures.as_deref())
+                    .expect("--dump-window requires --dump-file or --signatures");
+                match parse_dump_window(window) {
+                    Ok((addr, size)) => {
+                        if let Err(e) =
+                            run_memory_dump(&elf, &stdin, addr, size, args.word_bytes, out_file)
+                        {
+                            println!("Warning: Failed to dump memory window: {}", e);
+                        }
+                    }
+                    Err(e) => println!("Warning: Invalid --dump-window: {}", e),
+                }
+            }
+
             let context = SP1Context::default();
             let (report, execution_duration) =
                 time_operation(|| prover.execute(&elf, &stdin, context.clone()));
@@ -198,6 +249,25 @@ fn main() {
             let (_, verify_compressed_duration) =
                 time_operation(|| prover.verify_compressed(&compress_proof, &vk));
 
+            let (batch_size, batch_verify_duration, batch_proofs_per_sec) =
+                if let Some(batch_size) = args.batch {
+                    let batch: Vec<_> =
+                        std::iter::repeat((compress_proof.clone(), vk.clone())).take(batch_size).collect();
+                    let (results, batch_verify_duration) =
+                        time_operation(|| prover.verify_many_compressed(&batch));
+                    for result in &results {
+                        result.as_ref().expect("batch verification failed");
+                    }
+                    let proofs_per_sec = batch_size as f64 / batch_verify_duration.as_secs_f64();
+                    println!(
+                        "Verified {} proofs in {:?} ({:.2} proofs/sec)",
+                        batch_size, batch_verify_duration, proofs_per_sec
+                    );
+                    (batch_size, batch_verify_duration, proofs_per_sec)
+                } else {
+                    (0, Duration::default(), 0.0)
+                };
+
             let (shrink_proof, shrink_duration) =
                 time_operation(|| prover.shrink(compress_proof.clone(), opts).unwrap());
 
@@ -248,6 +318,9 @@ fn main() {
                 verify_shrink_duration,
                 wrap_duration,
                 verify_wrap_duration,
+                batch_size,
+                batch_verify_duration,
+                batch_proofs_per_sec,
             };
 
             println!("{:?}", result);
@@ -0,0 +1,169 @@
+//! Helpers for working with the riscv-arch-test signature convention: a
+//! `begin_signature`/`end_signature`-delimited region of memory that a test
+//! program fills in before halting, which is then dumped as one hex word
+//! per line (at a configurable word granularity) and compared against a
+//! golden reference.
+
+use std::{
+    fmt::Write as _,
+    io::{self, BufWriter, Write as _},
+};
+
+use elf::{abi::STT_OBJECT, endian::AnyEndian, ElfBytes};
+use sp1_core_executor::Executor;
+
+/// The default, and most common, signature word granularity: one 32-bit
+/// word per line, as `rv32` targets emit.
+pub const DEFAULT_WORD_BYTES: usize = 4;
+
+/// Number of words buffered in memory before a [`stream_memory`] call
+/// flushes them to its writer.
+const STREAM_CHUNK_WORDS: usize = 4096;
+
+/// Parse ELF symbols to find signature region boundaries.
+pub fn parse_signature_symbols(elf_data: &[u8]) -> Result<(u32, usize), Box<dyn std::error::Error>> {
+    let elf = ElfBytes::<AnyEndian>::minimal_parse(elf_data)?;
+
+    let (symbol_table, string_table) =
+        elf.symbol_table()?.ok_or("No symbol table found")?;
+
+    let mut begin_signature_addr: Option<u64> = None;
+    let mut end_signature_addr: Option<u64> = None;
+
+    for symbol in symbol_table.iter() {
+        if symbol.st_symtype() == STT_OBJECT || symbol.st_symtype() == elf::abi::STT_NOTYPE {
+            if let Ok(name) = string_table.get(symbol.st_name as usize) {
+                match name {
+                    "begin_signature" => {
+                        begin_signature_addr = Some(symbol.st_value);
+                    }
+                    "end_signature" => {
+                        end_signature_addr = Some(symbol.st_value);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    if let (Some(begin_addr), Some(end_addr)) = (begin_signature_addr, end_signature_addr) {
+        let size = (end_addr - begin_addr) as usize;
+        Ok((begin_addr as u32, size))
+    } else {
+        Err("Could not find both begin_signature and end_signature symbols".into())
+    }
+}
+
+/// Parse the ELF's `tohost` symbol address, if it defines one.
+///
+/// riscv-arch-test/RISCOF programs signal completion (and pass/fail) to the
+/// simulation environment by writing a status word to `tohost` rather than
+/// simply falling off the end of `main`; see [`read_tohost_status`] for how
+/// that word is interpreted. Not every compiled test defines the symbol
+/// (plain signature-only tests may not), so this returns `None` rather than
+/// erroring when it's absent.
+pub fn parse_tohost_symbol(elf_data: &[u8]) -> Result<Option<u32>, Box<dyn std::error::Error>> {
+    let elf = ElfBytes::<AnyEndian>::minimal_parse(elf_data)?;
+    let (symbol_table, string_table) =
+        elf.symbol_table()?.ok_or("No symbol table found")?;
+
+    for symbol in symbol_table.iter() {
+        if symbol.st_symtype() == STT_OBJECT || symbol.st_symtype() == elf::abi::STT_NOTYPE {
+            if string_table.get(symbol.st_name as usize) == Ok("tohost") {
+                return Ok(Some(symbol.st_value as u32));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// The outcome a test program reports through its `tohost` word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TohostStatus {
+    /// The program never wrote to `tohost` (it's still zero). Either the test hasn't
+    /// reached its exit point, or (for a finished run) it doesn't use this convention.
+    NotReported,
+    /// `tohost == 1`: the riscv-tests/RISCOF convention for "all checks passed".
+    Pass,
+    /// `tohost` holds any other value. Under the convention that value is
+    /// `(test_case << 1) | 1`; `test_case` is the value shifted right by one.
+    Fail { test_case: u32 },
+}
+
+/// Read and interpret the 32-bit `tohost` word at `addr` out of executor memory.
+pub fn read_tohost_status(executor: &mut Executor, addr: u32) -> TohostStatus {
+    let mut word_bytes = [0u8; 4];
+    for (j, byte) in word_bytes.iter_mut().enumerate() {
+        *byte = executor.byte(addr + j as u32);
+    }
+    let word = u32::from_le_bytes(word_bytes);
+    match word {
+        0 => TohostStatus::NotReported,
+        1 => TohostStatus::Pass,
+        other => TohostStatus::Fail { test_case: other >> 1 },
+    }
+}
+
+/// Read one word of `word_bytes` (1, 2, 4, or 8) out of executor memory at `addr`,
+/// zero-padding any read past `size` bytes from the region's start, matching
+/// [`Executor::byte`]'s behavior for memory the executor never wrote.
+///
+/// # Panics
+/// Panics if `word_bytes` is zero or greater than 8.
+fn read_word(executor: &mut Executor, addr: u32, offset: usize, size: usize, word_bytes: usize) -> u64 {
+    assert!((1..=8).contains(&word_bytes), "word_bytes must be between 1 and 8, got {word_bytes}");
+    let mut bytes = [0u8; 8];
+    for j in 0..word_bytes {
+        if offset + j < size {
+            bytes[j] = executor.byte(addr + (offset + j) as u32);
+        }
+    }
+    u64::from_le_bytes(bytes)
+}
+
+/// Collect signature data from executor memory, `word_bytes` bytes at a time.
+pub fn collect_signatures(
+    executor: &mut Executor,
+    addr: u32,
+    size: usize,
+    word_bytes: usize,
+) -> Vec<u64> {
+    (0..size).step_by(word_bytes).map(|i| read_word(executor, addr, i, size, word_bytes)).collect()
+}
+
+/// Stream `size` bytes of executor memory starting at `addr`, one hex word of
+/// `word_bytes` bytes per line, into `writer` in bounded chunks rather than
+/// materializing the whole region as a `Vec` and a `String` first. Works equally
+/// well for the symbol-delimited signature region or an arbitrary
+/// `[addr, addr + size)` memory window (e.g. for a debug dump).
+///
+/// Reads past the end of a non-word-aligned `size` are zero-padded, and reads of
+/// memory the executor never wrote come back as zero, matching [`Executor::byte`]'s
+/// behavior for both.
+pub fn stream_memory<W: io::Write>(
+    executor: &mut Executor,
+    addr: u32,
+    size: usize,
+    word_bytes: usize,
+    writer: W,
+) -> io::Result<usize> {
+    let mut writer = BufWriter::new(writer);
+    let hex_digits = word_bytes * 2;
+    let mut chunk = String::with_capacity(STREAM_CHUNK_WORDS * (hex_digits + 1));
+    let mut words_written = 0;
+
+    for i in (0..size).step_by(word_bytes) {
+        let word = read_word(executor, addr, i, size, word_bytes);
+        let _ = writeln!(chunk, "{:0width$x}", word, width = hex_digits);
+        words_written += 1;
+
+        if chunk.len() >= STREAM_CHUNK_WORDS * (hex_digits + 1) {
+            writer.write_all(chunk.as_bytes())?;
+            chunk.clear();
+        }
+    }
+
+    writer.write_all(chunk.as_bytes())?;
+    writer.flush()?;
+    Ok(words_written)
+}